@@ -6,7 +6,7 @@
 
 mod poison;
 
-use poison::PoisonError;
+pub use poison::PoisonError;
 
 impl core::fmt::Display for PoisonError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -14,6 +14,48 @@ impl core::fmt::Display for PoisonError {
     }
 }
 
+/// The error returned by [`Transitionable::try_transition_with`].
+pub enum TransitionError<E> {
+    /// The transition function returned `Err`, and the `Transitionable` has been poisoned as a
+    /// result since there was no new value to write back.
+    Failed(E),
+    /// The `Transitionable` was already poisoned before the transition was attempted.
+    #[cfg(not(panic = "abort"))]
+    Poisoned,
+}
+
+impl<E: core::fmt::Debug> core::fmt::Debug for TransitionError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Failed(e) => f.debug_tuple("Failed").field(e).finish(),
+            #[cfg(not(panic = "abort"))]
+            Self::Poisoned => f.debug_tuple("Poisoned").finish(),
+        }
+    }
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for TransitionError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Failed(e) => write!(f, "transition failed: {e}"),
+            #[cfg(not(panic = "abort"))]
+            Self::Poisoned => {
+                "poisoned transitionable: lost value due to panic in previous transition".fmt(f)
+            }
+        }
+    }
+}
+
+impl<E: core::error::Error + 'static> core::error::Error for TransitionError<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Failed(e) => Some(e),
+            #[cfg(not(panic = "abort"))]
+            Self::Poisoned => None,
+        }
+    }
+}
+
 /// This type can be used in places where you have an `&mut T` but need a `T`. It is similar to
 /// `Option` but with a more limited API that allows an optimization when the panic strategy is
 /// abort.
@@ -46,8 +88,10 @@ impl<T> Transitionable<T> {
         Self::try_into_inner(transitionable).unwrap()
     }
 
+    /// Deconstructs the `Transitionable` back into its held value, or a `PoisonError` if it is
+    /// poisoned.
     #[inline]
-    fn try_into_inner(transitionable: Self) -> Result<T, PoisonError> {
+    pub fn try_into_inner(transitionable: Self) -> Result<T, PoisonError> {
         match transitionable.0 {
             Inner::Ok(value) => Ok(value),
             #[cfg(not(panic = "abort"))]
@@ -73,8 +117,10 @@ impl<T> Transitionable<T> {
         Self::try_transition(transitionable, f).unwrap()
     }
 
+    /// Transition the held value from one state to the next through the provided function, or
+    /// return a `PoisonError` if the `Transitionable` is already poisoned.
     #[inline]
-    fn try_transition<F: FnOnce(T) -> T>(
+    pub fn try_transition<F: FnOnce(T) -> T>(
         transitionable: &mut Self,
         f: F,
     ) -> Result<&mut Self, PoisonError> {
@@ -98,11 +144,113 @@ impl<T> Transitionable<T> {
         Ok(transitionable)
     }
 
+    /// Transition the held value from one state to the next through a fallible function.
+    ///
+    /// On `Ok(next)` the new value is stored and `Ok(transitionable)` is returned. On `Err(e)`
+    /// there is no new value to write back, so the `Transitionable` is poisoned and
+    /// `Err(TransitionError::Failed(e))` is returned. If the `Transitionable` was already
+    /// poisoned, `Err(TransitionError::Poisoned)` is returned and `f` is not called.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use transitionable::{Transitionable, TransitionError};
+    /// enum State { A, B }
+    /// let t = &mut Transitionable::new(State::A);
+    /// let result = Transitionable::try_transition_with(t, |_: State| Err::<State, _>("no"));
+    /// assert!(matches!(result, Err(TransitionError::Failed("no"))));
+    /// ```
+    #[cfg(not(panic = "abort"))]
+    #[inline]
+    pub fn try_transition_with<E, F: FnOnce(T) -> Result<T, E>>(
+        transitionable: &mut Self,
+        f: F,
+    ) -> Result<&mut Self, TransitionError<E>> {
+        let value = match core::mem::replace(&mut transitionable.0, Inner::Poisoned) {
+            Inner::Ok(value) => value,
+            Inner::Poisoned => return Err(TransitionError::Poisoned),
+        };
+        match f(value) {
+            Ok(next) => {
+                transitionable.0 = Inner::Ok(next);
+                Ok(transitionable)
+            }
+            Err(e) => Err(TransitionError::Failed(e)),
+        }
+    }
+
+    /// Transition the held value from one state to the next through a fallible function.
+    ///
+    /// Because the panic strategy is abort, there is no `Inner::Poisoned` state to transition
+    /// into on failure, so the error variant must hand the value back to keep the
+    /// abort-strategy `ptr::read` optimization below sound.
+    ///
+    /// On `Ok(next)` the new value is stored and `Ok(transitionable)` is returned. On
+    /// `Err((value, e))` `value` is written back unchanged and `Err(TransitionError::Failed(e))`
+    /// is returned.
+    #[cfg(panic = "abort")]
+    #[inline]
+    pub fn try_transition_with<E, F: FnOnce(T) -> Result<T, (T, E)>>(
+        transitionable: &mut Self,
+        f: F,
+    ) -> Result<&mut Self, TransitionError<E>> {
+        // SAFETY: We are guaranteed to overwrite the temporarily duplicated value since the
+        // error variant hands `value` back, and the panic strategy is abort.
+        unsafe {
+            let Inner::Ok(value) = core::ptr::read(&transitionable.0);
+            match f(value) {
+                Ok(next) => {
+                    core::ptr::write(&mut transitionable.0, Inner::Ok(next));
+                    Ok(transitionable)
+                }
+                Err((value, e)) => {
+                    core::ptr::write(&mut transitionable.0, Inner::Ok(value));
+                    Err(TransitionError::Failed(e))
+                }
+            }
+        }
+    }
+
+    /// Consumes the `Transitionable`, transitioning the held value into a new state that may
+    /// have a different type.
+    ///
+    /// Unlike `transition`, which requires `F: FnOnce(T) -> T`, this lets typestate machines
+    /// encode `State::A -> State::B -> State::C` as real type changes rather than enum variants.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use transitionable::Transitionable;
+    /// struct A;
+    /// struct B;
+    /// let t = Transitionable::new(A);
+    /// let t: Transitionable<B> = Transitionable::transition_into(t, |_: A| B);
+    /// ```
+    /// # Panics
+    ///
+    /// Panics if the `Transitionable` is poisoned.
+    #[inline]
+    pub fn transition_into<U, F: FnOnce(T) -> U>(transitionable: Self, f: F) -> Transitionable<U> {
+        Self::try_transition_into(transitionable, f).unwrap()
+    }
+
+    /// Consumes the `Transitionable`, transitioning the held value into a new state that may
+    /// have a different type, or returns a `PoisonError` if the `Transitionable` is poisoned.
+    #[inline]
+    pub fn try_transition_into<U, F: FnOnce(T) -> U>(
+        transitionable: Self,
+        f: F,
+    ) -> Result<Transitionable<U>, PoisonError> {
+        Ok(Transitionable(Inner::Ok(f(Self::try_into_inner(
+            transitionable,
+        )?))))
+    }
+
     /// A `Transitionable` becomes poisoned when a panic occurs inside the function passed to
     /// `Transitionable::transition`. To use a `Transitionable` in an application that may catch and recover
     /// from panics, you can use this function to determine whether a `Transitionable` is poisoned.
-    /// If a `Transitionable` is poisoned, you will have to replace it by creating a new
-    /// `Transitionable`.
+    /// If a `Transitionable` is poisoned, you can replace it by creating a new `Transitionable`,
+    /// or use `Transitionable::recover` to install a fresh value in place.
     #[inline]
     pub const fn is_poisoned(transitionable: &Self) -> bool {
         #[cfg(not(panic = "abort"))]
@@ -116,13 +264,75 @@ impl<T> Transitionable<T> {
         }
     }
 
+    /// If the `Transitionable` is poisoned, installs a fresh value produced by `f` and clears the
+    /// poisoned state. This is a no-op if the `Transitionable` is healthy, in which case `f` is
+    /// not called.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use transitionable::Transitionable;
+    /// enum State { A, B }
+    /// let t = &mut Transitionable::new(State::A);
+    /// Transitionable::recover(t, || State::B);
+    /// assert!(matches!(**t, State::A));
+    /// ```
+    #[inline]
+    pub fn recover<F: FnOnce() -> T>(transitionable: &mut Self, f: F) -> &mut Self {
+        #[cfg(not(panic = "abort"))]
+        {
+            if let Inner::Poisoned = transitionable.0 {
+                transitionable.0 = Inner::Ok(f());
+            }
+        }
+        #[cfg(panic = "abort")]
+        {
+            _ = f;
+        }
+        transitionable
+    }
+
+    /// Installs a fresh value produced by `f`, which is given the current value if the
+    /// `Transitionable` is healthy, or `None` if it is poisoned. Clears the poisoned state.
+    ///
+    /// This is `Transitionable::recover`'s counterpart for when a healthy value should be
+    /// transformed rather than left alone.
     #[inline]
-    fn get(transitionable: &Self) -> &T {
+    pub fn recover_with<F: FnOnce(Option<T>) -> T>(transitionable: &mut Self, f: F) -> &mut Self {
+        #[cfg(not(panic = "abort"))]
+        {
+            match core::mem::replace(&mut transitionable.0, Inner::Poisoned) {
+                Inner::Ok(value) => transitionable.0 = Inner::Ok(f(Some(value))),
+                Inner::Poisoned => transitionable.0 = Inner::Ok(f(None)),
+            }
+        }
+        #[cfg(panic = "abort")]
+        {
+            // SAFETY: We are guaranteed to overwrite the temporarily duplicated value since the
+            // panic strategy is abort, and abort builds can never observe `Inner::Poisoned`, so
+            // `f` is always called with `Some`.
+            unsafe {
+                let Inner::Ok(value) = core::ptr::read(&transitionable.0);
+                core::ptr::write(&mut transitionable.0, Inner::Ok(f(Some(value))));
+            }
+        }
+        transitionable
+    }
+
+    /// Returns a reference to the held value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `Transitionable` is poisoned.
+    #[inline]
+    pub fn get(transitionable: &Self) -> &T {
         Self::try_get(transitionable).unwrap()
     }
 
+    /// Returns a reference to the held value, or a `PoisonError` if the `Transitionable` is
+    /// poisoned.
     #[inline]
-    fn try_get(transitionable: &Self) -> Result<&T, PoisonError> {
+    pub fn try_get(transitionable: &Self) -> Result<&T, PoisonError> {
         match &transitionable.0 {
             Inner::Ok(value) => Ok(value),
             #[cfg(not(panic = "abort"))]
@@ -130,19 +340,131 @@ impl<T> Transitionable<T> {
         }
     }
 
+    /// Returns a mutable reference to the held value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `Transitionable` is poisoned.
     #[inline]
-    fn get_mut(transitionable: &mut Self) -> &mut T {
+    pub fn get_mut(transitionable: &mut Self) -> &mut T {
         Self::try_get_mut(transitionable).unwrap()
     }
 
+    /// Returns a mutable reference to the held value, or a `PoisonError` if the `Transitionable`
+    /// is poisoned.
     #[inline]
-    fn try_get_mut(transitionable: &mut Self) -> Result<&mut T, PoisonError> {
+    pub fn try_get_mut(transitionable: &mut Self) -> Result<&mut T, PoisonError> {
         match &mut transitionable.0 {
             Inner::Ok(value) => Ok(value),
             #[cfg(not(panic = "abort"))]
             Inner::Poisoned => Err(PoisonError::new()),
         }
     }
+
+    /// Takes the held value out into an RAII guard that `Deref`s/`DerefMut`s to `T`, so the
+    /// caller can perform several in-place mutations before writing the result back with
+    /// [`TransitionGuard::commit`].
+    ///
+    /// If the guard is dropped without calling `commit` — because a panic unwound through it, or
+    /// because a `?` returned early — the `Transitionable` is left poisoned. This supports
+    /// fallible, multi-statement edits that the single-closure `transition` cannot express.
+    ///
+    /// Under `panic = "abort"` there is no poisoned state to fall back to: dropping the guard
+    /// without calling `commit` aborts the process instead (see `TransitionGuard`'s `Drop` impl).
+    ///
+    /// Returns a `PoisonError` if the `Transitionable` is already poisoned.
+    #[inline]
+    pub fn guard(transitionable: &mut Self) -> Result<TransitionGuard<'_, T>, PoisonError> {
+        #[cfg(not(panic = "abort"))]
+        let value = match core::mem::replace(&mut transitionable.0, Inner::Poisoned) {
+            Inner::Ok(value) => value,
+            Inner::Poisoned => return Err(PoisonError::new()),
+        };
+        #[cfg(panic = "abort")]
+        // SAFETY: We are guaranteed to overwrite the temporarily duplicated value, either by
+        // `TransitionGuard::commit` or by its `Drop` impl, since the panic strategy is abort.
+        let value = unsafe {
+            let Inner::Ok(value) = core::ptr::read(&transitionable.0);
+            value
+        };
+        Ok(TransitionGuard {
+            transitionable,
+            value: core::mem::ManuallyDrop::new(value),
+        })
+    }
+}
+
+/// An RAII guard holding the value taken out of a `Transitionable` by [`Transitionable::guard`].
+///
+/// `Deref`s/`DerefMut`s to `T` so the caller can perform several in-place mutations. Call
+/// [`TransitionGuard::commit`] to write the (possibly modified) value back and clear poison.
+/// Dropping the guard without committing leaves the source `Transitionable` poisoned — except
+/// under `panic = "abort"`, where there is no poisoned state to fall back to and a non-committed
+/// drop aborts the process instead.
+pub struct TransitionGuard<'a, T> {
+    transitionable: &'a mut Transitionable<T>,
+    value: core::mem::ManuallyDrop<T>,
+}
+
+impl<T> TransitionGuard<'_, T> {
+    /// Writes the (possibly modified) value back into the source `Transitionable` and clears
+    /// poison.
+    #[inline]
+    pub fn commit(mut guard: Self) {
+        // SAFETY: `guard` is forgotten below, so `value` is not accessed again and `Drop` does
+        // not run and poison the source.
+        let value = unsafe { core::mem::ManuallyDrop::take(&mut guard.value) };
+        #[cfg(not(panic = "abort"))]
+        {
+            guard.transitionable.0 = Inner::Ok(value);
+        }
+        #[cfg(panic = "abort")]
+        // SAFETY: Under the abort strategy, `guard()` duplicated the value into
+        // `transitionable.0` via `ptr::read` without dropping the original place. A normal
+        // assignment here would drop that live duplicate, double-freeing it; `ptr::write`
+        // overwrites it without running its destructor.
+        unsafe {
+            core::ptr::write(&mut guard.transitionable.0, Inner::Ok(value));
+        }
+        core::mem::forget(guard);
+    }
+}
+
+impl<T> core::ops::Deref for TransitionGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T> core::ops::DerefMut for TransitionGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<T> Drop for TransitionGuard<'_, T> {
+    fn drop(&mut self) {
+        #[cfg(not(panic = "abort"))]
+        {
+            self.transitionable.0 = Inner::Poisoned;
+        }
+        #[cfg(panic = "abort")]
+        {
+            // There is no `Inner::Poisoned` state to fall back to when the panic strategy is
+            // abort, so dropping the guard without calling `commit` would leave a type-confused
+            // hole behind. Escalate instead: with `panic = "abort"` a panic terminates the
+            // process immediately rather than unwinding.
+            panic!("TransitionGuard dropped without calling `commit` under panic = \"abort\"");
+        }
+        #[cfg(not(panic = "abort"))]
+        // SAFETY: `commit` is the only other consumer of `value`, and it forgets `self` before
+        // returning, so `drop` always owns a not-yet-taken value here.
+        unsafe {
+            core::mem::ManuallyDrop::drop(&mut self.value)
+        };
+    }
 }
 
 impl<T> From<T> for Transitionable<T> {
@@ -234,4 +556,30 @@ pub mod tests {
         let mut t = poisoned();
         let _: &mut () = &mut t;
     }
+
+    #[cfg_attr(test, test)]
+    pub fn transition_into_works() {
+        let t = Transitionable::new(1_u32);
+        let t = Transitionable::transition_into(t, |x: u32| x.to_string());
+        assert_eq!(*t, "1");
+    }
+
+    #[cfg_attr(test, test)]
+    pub fn guard_commit_works() {
+        let mut t = Transitionable::new(1);
+        let guard = Transitionable::guard(&mut t).unwrap();
+        TransitionGuard::commit(guard);
+        assert!(!Transitionable::is_poisoned(&t));
+        assert_eq!(*t, 1);
+    }
+
+    #[cfg(not(panic = "abort"))]
+    #[cfg_attr(test, test)]
+    pub fn guard_drop_without_commit_poisons() {
+        let mut t = Transitionable::new(1);
+        {
+            let _guard = Transitionable::guard(&mut t).unwrap();
+        }
+        assert!(Transitionable::is_poisoned(&t));
+    }
 }
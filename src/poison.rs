@@ -3,7 +3,7 @@
 
 /// The error that is returned when attempting to use a `crate::Transitionable` that has been
 /// poisoned.
-pub(crate) struct PoisonError {
+pub struct PoisonError {
     _private: (),
 
     #[cfg(panic = "abort")]